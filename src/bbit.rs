@@ -0,0 +1,141 @@
+//! b-bit MinHash compression: keep only the lowest `b` bits of each hash
+//! slot, packed into a bit-dense signature, trading memory against the
+//! precision of the resemblance estimate.
+
+/// Bit-packed storage for a b-bit-truncated MinHash signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitVec {
+  bits: Vec<u8>,
+  len: usize,
+}
+
+impl BitVec {
+  fn with_bit_len(len: usize) -> Self {
+    BitVec {
+      bits: vec![0; len.div_ceil(8)],
+      len,
+    }
+  }
+
+  fn set(&mut self, index: usize, value: bool) {
+    let byte = index / 8;
+    let bit = index % 8;
+    if value {
+      self.bits[byte] |= 1 << bit;
+    } else {
+      self.bits[byte] &= !(1 << bit);
+    }
+  }
+
+  fn get(&self, index: usize) -> bool {
+    let byte = index / 8;
+    let bit = index % 8;
+    (self.bits[byte] >> bit) & 1 == 1
+  }
+
+  /// Number of bits stored.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+}
+
+/// Packs `hashes` into a [`BitVec`], keeping only the lowest `b` bits of
+/// each hash value.
+pub fn pack_bbit(hashes: &[usize], b: usize) -> BitVec {
+  assert!(
+    (1..=usize::BITS as usize).contains(&b),
+    "b must be between 1 and {} bits",
+    usize::BITS
+  );
+
+  let mut packed = BitVec::with_bit_len(hashes.len() * b);
+  for (slot, &hash) in hashes.iter().enumerate() {
+    for bit_index in 0..b {
+      packed.set(slot * b + bit_index, (hash >> bit_index) & 1 == 1);
+    }
+  }
+  packed
+}
+
+/// Estimates Jaccard similarity between two b-bit-compressed signatures.
+///
+/// Truncating each hash to `b` bits introduces accidental collisions, so
+/// the raw match fraction `P_b` is bias-corrected: `(P_b - 2^-b) / (1 -
+/// 2^-b)`, clamped to `[0, 1]`. Larger `b` shrinks the `2^-b` correction
+/// term (more accuracy) at the cost of `k * b` bits per signature instead
+/// of `k` full hashes; `b = 1` or `b = 2` is usually enough for
+/// high-similarity dedup.
+pub fn estimate_jaccard_bbit(sig1: &BitVec, sig2: &BitVec, b: usize) -> f64 {
+  assert!(
+    (1..=usize::BITS as usize).contains(&b),
+    "b must be between 1 and {} bits",
+    usize::BITS
+  );
+  assert_eq!(sig1.len(), sig2.len(), "signatures must have the same length");
+  assert_eq!(sig1.len() % b, 0, "signature length must be a multiple of b");
+
+  let k = sig1.len() / b;
+  let matches = (0..k)
+    .filter(|&slot| {
+      (0..b).all(|bit_index| {
+        sig1.get(slot * b + bit_index) == sig2.get(slot * b + bit_index)
+      })
+    })
+    .count();
+
+  let p_b = matches as f64 / k as f64;
+  let collision_prob = (0.5_f64).powi(b as i32);
+  ((p_b - collision_prob) / (1.0 - collision_prob)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_pack_bbit_keeps_only_lowest_bits() {
+    let packed = pack_bbit(&[0b110, 0b101], 2);
+    assert_eq!(packed.len(), 4);
+    assert!(!packed.get(0)); // bit 0 of 0b110
+    assert!(packed.get(1)); // bit 1 of 0b110
+    assert!(packed.get(2)); // bit 0 of 0b101
+    assert!(!packed.get(3)); // bit 1 of 0b101
+  }
+
+  #[test]
+  #[should_panic(expected = "between 1 and")]
+  fn test_pack_bbit_rejects_zero_b() {
+    pack_bbit(&[1, 2, 3], 0);
+  }
+
+  #[test]
+  #[should_panic(expected = "between 1 and")]
+  fn test_pack_bbit_rejects_b_over_word_width() {
+    pack_bbit(&[1, 2, 3], 70);
+  }
+
+  #[test]
+  #[should_panic(expected = "between 1 and")]
+  fn test_estimate_jaccard_bbit_rejects_zero_b() {
+    let packed = pack_bbit(&[1, 2, 3], 1);
+    estimate_jaccard_bbit(&packed, &packed, 0);
+  }
+
+  #[test]
+  fn test_estimate_jaccard_bbit_identical_signatures_is_one() {
+    let packed = pack_bbit(&[1, 2, 3, 4, 5, 6, 7, 8], 2);
+    assert_eq!(estimate_jaccard_bbit(&packed, &packed, 2), 1.0);
+  }
+
+  #[test]
+  fn test_estimate_jaccard_bbit_matches_theoretical_bound() {
+    let a = pack_bbit(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 1);
+    let b = pack_bbit(&[0, 1, 2, 3, 4, 100, 101, 102, 103, 104], 1);
+    let estimate = estimate_jaccard_bbit(&a, &b, 1);
+    assert!((0.0..=1.0).contains(&estimate));
+  }
+}
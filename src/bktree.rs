@@ -0,0 +1,128 @@
+//! BK-tree index over MinHash signatures for sublinear threshold queries
+//! ("every item within `t` mismatching slots of a query").
+
+use std::collections::HashMap;
+
+/// Number of slots where two equal-length signatures differ. This is a
+/// metric over signatures (symmetric, obeys the triangle inequality) and
+/// equals `k` minus the estimated-similarity match count.
+pub fn hamming_distance(a: &[usize], b: &[usize]) -> usize {
+  assert_eq!(a.len(), b.len(), "signatures must have the same length");
+  a.iter().zip(b).filter(|(x, y)| x != y).count()
+}
+
+struct Node {
+  signature: Vec<usize>,
+  children: HashMap<usize, Node>,
+}
+
+impl Node {
+  fn insert(&mut self, signature: Vec<usize>) {
+    let distance = hamming_distance(&self.signature, &signature);
+    match self.children.get_mut(&distance) {
+      Some(child) => child.insert(signature),
+      None => {
+        self.children.insert(
+          distance,
+          Node {
+            signature,
+            children: HashMap::new(),
+          },
+        );
+      }
+    }
+  }
+
+  fn query(&self, query: &[usize], t: usize, results: &mut Vec<Vec<usize>>) {
+    let distance = hamming_distance(&self.signature, query);
+    if distance <= t {
+      results.push(self.signature.clone());
+    }
+
+    for (&edge_label, child) in &self.children {
+      if edge_label.abs_diff(distance) <= t {
+        child.query(query, t, results);
+      }
+    }
+  }
+}
+
+/// Metric tree over fixed-length signatures, indexed by Hamming distance.
+/// Insertion walks down following the child whose edge label equals the
+/// new signature's distance to the current node, creating one if none
+/// exists. Queries prune any subtree whose edge label can't be within `t`
+/// of the query's distance to the current node.
+pub struct BkTree {
+  root: Option<Node>,
+}
+
+impl BkTree {
+  pub fn new() -> Self {
+    BkTree { root: None }
+  }
+
+  /// Inserts `signature` into the tree.
+  pub fn insert(&mut self, signature: Vec<usize>) {
+    match &mut self.root {
+      None => {
+        self.root = Some(Node {
+          signature,
+          children: HashMap::new(),
+        })
+      }
+      Some(root) => root.insert(signature),
+    }
+  }
+
+  /// Returns every inserted signature within Hamming distance `t` of
+  /// `query`.
+  pub fn query(&self, query: &[usize], t: usize) -> Vec<Vec<usize>> {
+    let mut results = Vec::new();
+    if let Some(root) = &self.root {
+      root.query(query, t, &mut results);
+    }
+    results
+  }
+}
+
+impl Default for BkTree {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_hamming_distance_basic() {
+    assert_eq!(hamming_distance(&[1, 2, 3], &[1, 2, 3]), 0);
+    assert_eq!(hamming_distance(&[1, 2, 3], &[1, 9, 9]), 2);
+  }
+
+  #[test]
+  #[should_panic(expected = "same length")]
+  fn test_hamming_distance_rejects_mismatched_lengths() {
+    hamming_distance(&[1, 2], &[1, 2, 3]);
+  }
+
+  #[test]
+  fn test_bktree_query_finds_items_within_threshold() {
+    let mut tree = BkTree::new();
+    tree.insert(vec![1, 1, 1, 1]);
+    tree.insert(vec![1, 1, 1, 9]);
+    tree.insert(vec![9, 9, 9, 9]);
+
+    let results = tree.query(&[1, 1, 1, 1], 1);
+    assert!(results.contains(&vec![1, 1, 1, 1]));
+    assert!(results.contains(&vec![1, 1, 1, 9]));
+    assert!(!results.contains(&vec![9, 9, 9, 9]));
+  }
+
+  #[test]
+  fn test_bktree_query_on_empty_tree_is_empty() {
+    let tree = BkTree::new();
+    assert!(tree.query(&[1, 2, 3], 5).is_empty());
+  }
+}
@@ -1,14 +1,88 @@
-struct CMinHash {
+pub mod bbit;
+pub mod bktree;
+pub mod lsh;
+
+use bbit::BitVec;
+
+pub struct CMinHash {
   sigma: Vec<usize>,
   pi: Vec<usize>,
   k: usize,
 }
 
+/// Minimal splitmix64-based PRNG so permutation generation is deterministic
+/// across runs/machines without pulling in an external RNG crate.
+struct SplitMix64 {
+  state: u64,
+}
+
+impl SplitMix64 {
+  fn new(seed: u64) -> Self {
+    SplitMix64 { state: seed }
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+  }
+
+  /// Uniform value in `[0, bound)`.
+  fn next_below(&mut self, bound: usize) -> usize {
+    (self.next_u64() % bound as u64) as usize
+  }
+
+  /// Uniform value in `(0, 1]`, never 0, so `ln()` of it never diverges.
+  /// (`2^53 + 1` rounds to `2^53` in `f64`, so the top draw yields 1.0.)
+  fn next_f64(&mut self) -> f64 {
+    let top53 = self.next_u64() >> 11;
+    (top53 as f64 + 1.0) / ((1u64 << 53) as f64 + 1.0)
+  }
+}
+
+/// Builds a uniformly random permutation of `[0, len)` with a seeded
+/// Fisher–Yates shuffle so the result is reproducible for a given seed.
+fn random_permutation(len: usize, rng: &mut SplitMix64) -> Vec<usize> {
+  let mut permutation: Vec<usize> = (0..len).collect();
+  for i in (1..len).rev() {
+    let j = rng.next_below(i + 1);
+    permutation.swap(i, j);
+  }
+  permutation
+}
+
 impl CMinHash {
   pub fn new(sigma: Vec<usize>, pi: Vec<usize>, k: usize) -> Self {
     CMinHash { sigma, pi, k }
   }
 
+  /// Builds a `CMinHash` from two caller-supplied permutations, validating
+  /// that they share a dimension. This is the manual path kept around for
+  /// callers who already have `sigma`/`pi`; prefer
+  /// [`CMinHash::with_dimension`] for randomized generation.
+  pub fn from_permutations(sigma: Vec<usize>, pi: Vec<usize>, k: usize) -> Self {
+    assert_eq!(
+      sigma.len(),
+      pi.len(),
+      "sigma and pi must be permutations of the same dimension"
+    );
+    Self::new(sigma, pi, k)
+  }
+
+  /// Generates a fresh `CMinHash` of dimension `d` by drawing two
+  /// independent permutations of `[0, d)` from a seeded PRNG: `sigma`
+  /// breaks the input's original order, and `pi` is the base permutation
+  /// that gets circulantly shifted `k` times to derive the `k` hash
+  /// functions. Same `seed` always produces the same permutations.
+  pub fn with_dimension(d: usize, k: usize, seed: u64) -> Self {
+    let mut rng = SplitMix64::new(seed);
+    let sigma = random_permutation(d, &mut rng);
+    let pi = random_permutation(d, &mut rng);
+    Self::new(sigma, pi, k)
+  }
+
   pub fn compute(&self, data: &Vec<bool>) -> Vec<usize> {
     let data_permuted = self.apply_permutation(data, &self.sigma);
 
@@ -56,6 +130,139 @@ impl CMinHash {
 
     shifted_pi
   }
+
+  /// Sparse counterpart to [`CMinHash::compute`]: instead of scanning a
+  /// dense `[0, d)` boolean vector, only the `tokens` that are actually
+  /// present participate in the min computation. Each token is mapped into
+  /// `[0, d)` via a universal hash before `sigma`/`pi` are applied, so
+  /// hashing a document only costs work proportional to its shingle count,
+  /// not the full vocabulary size `d`.
+  pub fn compute_set(&self, tokens: &[u64]) -> Vec<usize> {
+    let d = self.sigma.len();
+    let active_positions: Vec<usize> = tokens
+      .iter()
+      .map(|&token| self.sigma[universal_hash(token, d)])
+      .collect();
+
+    let mut hashes = Vec::with_capacity(self.k);
+    for k_index in 0..self.k {
+      // Read the circulant-shifted pi directly instead of materializing a
+      // length-d shifted copy: shifted[(i + s) % d] = pi[i].
+      let hash_value = active_positions
+        .iter()
+        .map(|&position| self.pi[(position + d - k_index % d) % d])
+        .min()
+        .unwrap_or(usize::MAX);
+
+      hashes.push(hash_value);
+    }
+
+    hashes
+  }
+
+  /// Builds overlapping character `k`-gram shingles from `text`, hashes
+  /// each shingle into a token id, and runs them through
+  /// [`CMinHash::compute_set`]. This is the usual way MinHash is applied to
+  /// free text, since the shingle set stands in for the document's sparse
+  /// feature vector.
+  pub fn compute_shingles(&self, text: &str, k: usize) -> Vec<usize> {
+    let chars: Vec<char> = text.chars().collect();
+    if k == 0 || chars.len() < k {
+      return self.compute_set(&[]);
+    }
+
+    let tokens: Vec<u64> = chars
+      .windows(k)
+      .map(|shingle| fnv1a_hash(shingle.iter().collect::<String>().as_bytes()))
+      .collect();
+
+    self.compute_set(&tokens)
+  }
+
+  /// Computes the signature and compresses it to `b` bits per slot via
+  /// [`bbit::pack_bbit`], for corpora too large to store full `usize`
+  /// hashes per signature.
+  pub fn compute_bbit(&self, data: &Vec<bool>, b: usize) -> BitVec {
+    bbit::pack_bbit(&self.compute(data), b)
+  }
+
+  /// Consistent weighted sampling (CWS), generalizing the signature to
+  /// real-valued `weights` (tf-idf, audio/image feature weights) instead
+  /// of binary set membership. For each of the `k` hash slots, every
+  /// nonzero-weight element draws a deterministic sample keyed by its
+  /// index and the slot index, and the element with the smallest "active
+  /// index" wins the slot. Two weighted sets' estimated weighted Jaccard
+  /// is the fraction of slots whose `(element, t)` sample matches.
+  pub fn compute_weighted(&self, weights: &[f64]) -> Vec<(usize, f64)> {
+    let mut samples = Vec::with_capacity(self.k);
+
+    for hash_index in 0..self.k {
+      let mut best: Option<(usize, f64, f64)> = None; // (element, t, active_index)
+
+      for (element_index, &weight) in weights.iter().enumerate() {
+        if weight <= 0.0 {
+          continue;
+        }
+
+        let mut rng = SplitMix64::new(cws_seed(element_index, hash_index));
+        let r = sample_gamma2(&mut rng);
+        let c = sample_gamma2(&mut rng);
+        let beta = rng.next_f64();
+
+        let t = (weight.ln() / r + beta).floor();
+        let y = (r * (t - beta)).exp();
+        let active_index = c / (y * r.exp());
+
+        if best.is_none_or(|(_, _, best_active_index)| active_index < best_active_index) {
+          best = Some((element_index, t, active_index));
+        }
+      }
+
+      let (element_index, t, _) = best.unwrap_or((usize::MAX, 0.0, f64::MAX));
+      samples.push((element_index, t));
+    }
+
+    samples
+  }
+}
+
+/// Derives a deterministic per-(element, hash slot) seed for consistent
+/// weighted sampling, reusing the crate's FNV-1a hash to combine the two
+/// indices.
+fn cws_seed(element_index: usize, hash_index: usize) -> u64 {
+  let mut bytes = (element_index as u64).to_le_bytes().to_vec();
+  bytes.extend_from_slice(&(hash_index as u64).to_le_bytes());
+  fnv1a_hash(&bytes)
+}
+
+/// Draws a Gamma(2, 1) sample as the sum of two Exp(1) samples, i.e.
+/// `-ln(u1) - ln(u2) = -ln(u1 * u2)`.
+fn sample_gamma2(rng: &mut SplitMix64) -> f64 {
+  -(rng.next_f64() * rng.next_f64()).ln()
+}
+
+/// Maps an arbitrary `u64` token into the `[0, d)` index domain with a
+/// fixed multiply-xorshift universal hash, so sparse token ids spread
+/// evenly across the permutation's range regardless of their original
+/// scale.
+fn universal_hash(token: u64, d: usize) -> usize {
+  let mut x = token ^ 0x9E3779B97F4A7C15;
+  x = x.wrapping_mul(0xBF58476D1CE4E5B9);
+  x ^= x >> 32;
+  (x % d as u64) as usize
+}
+
+/// FNV-1a hash, used to turn a shingle's bytes into a `u64` token id.
+pub(crate) fn fnv1a_hash(bytes: &[u8]) -> u64 {
+  const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+  const FNV_PRIME: u64 = 0x100000001b3;
+
+  let mut hash = FNV_OFFSET;
+  for &byte in bytes {
+    hash ^= byte as u64;
+    hash = hash.wrapping_mul(FNV_PRIME);
+  }
+  hash
 }
 
 #[cfg(test)]
@@ -77,6 +284,33 @@ mod tests {
     assert_eq!(shifted_pi, vec![0, 1, 2]);
   }
 
+  #[test]
+  fn test_with_dimension_produces_valid_permutations() {
+    let cminhash = CMinHash::with_dimension(10, 4, 42);
+
+    let mut sorted_sigma = cminhash.sigma.clone();
+    sorted_sigma.sort_unstable();
+    assert_eq!(sorted_sigma, (0..10).collect::<Vec<_>>());
+
+    let mut sorted_pi = cminhash.pi.clone();
+    sorted_pi.sort_unstable();
+    assert_eq!(sorted_pi, (0..10).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn test_with_dimension_is_deterministic_for_seed() {
+    let a = CMinHash::with_dimension(20, 4, 7);
+    let b = CMinHash::with_dimension(20, 4, 7);
+    assert_eq!(a.sigma, b.sigma);
+    assert_eq!(a.pi, b.pi);
+  }
+
+  #[test]
+  #[should_panic(expected = "same dimension")]
+  fn test_from_permutations_rejects_mismatched_lengths() {
+    CMinHash::from_permutations(vec![0, 1], vec![0, 1, 2], 2);
+  }
+
   #[test]
   fn test_compute_basic() {
     let cminhash = CMinHash::new(vec![0, 2, 1], vec![1, 0, 2], 3);
@@ -86,6 +320,107 @@ mod tests {
     assert_eq!(hashes, expected_hashes);
   }
 
+  #[test]
+  fn test_compute_set_matches_dense_compute_for_full_vocabulary() {
+    let cminhash = CMinHash::with_dimension(16, 8, 1);
+    let tokens: Vec<u64> = (0..16).collect();
+
+    let mut dense = vec![false; 16];
+    for &token in &tokens {
+      dense[universal_hash(token, 16)] = true;
+    }
+
+    assert_eq!(cminhash.compute_set(&tokens), cminhash.compute(&dense));
+  }
+
+  #[test]
+  fn test_compute_set_empty_tokens_is_all_max() {
+    let cminhash = CMinHash::with_dimension(8, 4, 2);
+    assert_eq!(cminhash.compute_set(&[]), vec![usize::MAX; 4]);
+  }
+
+  #[test]
+  fn test_compute_shingles_is_deterministic_and_sensitive_to_k() {
+    let cminhash = CMinHash::with_dimension(256, 16, 3);
+    let a = cminhash.compute_shingles("the quick brown fox", 3);
+    let b = cminhash.compute_shingles("the quick brown fox", 3);
+    assert_eq!(a, b);
+
+    let different_k = cminhash.compute_shingles("the quick brown fox", 4);
+    assert_ne!(a, different_k);
+  }
+
+  #[test]
+  fn test_compute_shingles_similar_text_has_similar_signature() {
+    let cminhash = CMinHash::with_dimension(512, 32, 5);
+    let a = cminhash.compute_shingles("the quick brown fox jumps", 3);
+    let b = cminhash.compute_shingles("the quick brown fox leaps", 3);
+    let unrelated = cminhash.compute_shingles("zzz completely different yyy", 3);
+
+    let matches_ab = a.iter().zip(&b).filter(|(x, y)| x == y).count();
+    let matches_unrelated = a
+      .iter()
+      .zip(&unrelated)
+      .filter(|(x, y)| x == y)
+      .count();
+    assert!(matches_ab > matches_unrelated);
+  }
+
+  #[test]
+  fn test_compute_bbit_packs_lowest_bits_of_signature() {
+    let cminhash = CMinHash::with_dimension(32, 8, 9);
+    let data = vec![true, false, true, true, false, false, true, false]
+      .into_iter()
+      .cycle()
+      .take(32)
+      .collect();
+
+    let hashes = cminhash.compute(&data);
+    let packed = cminhash.compute_bbit(&data, 2);
+    assert_eq!(packed, bbit::pack_bbit(&hashes, 2));
+  }
+
+  #[test]
+  fn test_compute_weighted_is_deterministic() {
+    let cminhash = CMinHash::with_dimension(8, 16, 11);
+    let weights = vec![1.0, 0.0, 2.5, 0.0, 3.0, 0.0, 0.0, 1.2];
+
+    let a = cminhash.compute_weighted(&weights);
+    let b = cminhash.compute_weighted(&weights);
+    assert_eq!(a, b);
+  }
+
+  #[test]
+  fn test_compute_weighted_ignores_zero_weight_elements() {
+    let cminhash = CMinHash::with_dimension(4, 8, 4);
+    let samples = cminhash.compute_weighted(&[0.0, 5.0, 0.0, 0.0]);
+    assert!(samples.iter().all(|&(element, _)| element == 1));
+  }
+
+  #[test]
+  fn test_compute_weighted_similar_weights_score_higher_than_unrelated() {
+    let cminhash = CMinHash::with_dimension(16, 64, 13);
+    let w1 = vec![
+      1.0, 2.0, 0.0, 0.0, 3.0, 0.0, 1.5, 0.0, 0.0, 2.2, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+    ];
+    let w2 = vec![
+      1.1, 2.1, 0.0, 0.0, 2.9, 0.0, 1.4, 0.0, 0.0, 2.3, 0.0, 0.0, 0.0, 0.9, 0.0, 0.0,
+    ];
+    let unrelated = vec![
+      0.0, 0.0, 5.0, 4.0, 0.0, 3.0, 0.0, 2.0, 6.0, 0.0, 1.0, 1.0, 2.0, 0.0, 3.0, 1.0,
+    ];
+
+    let s1 = cminhash.compute_weighted(&w1);
+    let s2 = cminhash.compute_weighted(&w2);
+    let su = cminhash.compute_weighted(&unrelated);
+
+    let matches = |a: &Vec<(usize, f64)>, b: &Vec<(usize, f64)>| {
+      a.iter().zip(b).filter(|(x, y)| x == y).count()
+    };
+
+    assert!(matches(&s1, &s2) > matches(&s1, &su));
+  }
+
   fn jaccard_similarity(v1: &Vec<bool>, v2: &Vec<bool>) -> f64 {
     let intersection = v1.iter().zip(v2).filter(|(&a, &b)| a && b).count();
     let union = v1.iter().zip(v2).filter(|(&a, &b)| a || b).count();
@@ -0,0 +1,138 @@
+//! LSH banding index for sublinear near-duplicate retrieval over MinHash
+//! signatures, instead of comparing every pair.
+
+use crate::fnv1a_hash;
+use std::collections::{HashMap, HashSet};
+
+/// Identifier for an item whose signature has been inserted into an
+/// [`LshIndex`].
+pub type ItemId = u64;
+
+/// Splits signatures of length `k = bands * rows` into bands and indexes
+/// each band's values into a bucket map, so items sharing a band bucket
+/// become candidate near-duplicates.
+pub struct LshIndex {
+  bands: usize,
+  rows: usize,
+  buckets: Vec<HashMap<u64, Vec<ItemId>>>,
+}
+
+impl LshIndex {
+  /// Creates an index for signatures split into `bands` bands of `rows`
+  /// rows each.
+  pub fn new(bands: usize, rows: usize) -> Self {
+    LshIndex {
+      bands,
+      rows,
+      buckets: vec![HashMap::new(); bands],
+    }
+  }
+
+  /// Indexes `id`'s `signature` under every band bucket it falls into.
+  pub fn insert(&mut self, id: ItemId, signature: &[usize]) {
+    self.assert_signature_len(signature);
+
+    for band in 0..self.bands {
+      let bucket_key = self.band_hash(signature, band);
+      self.buckets[band].entry(bucket_key).or_default().push(id);
+    }
+  }
+
+  /// Returns every inserted item sharing at least one band bucket with
+  /// `signature`, i.e. the candidate near-duplicates.
+  pub fn query(&self, signature: &[usize]) -> Vec<ItemId> {
+    self.assert_signature_len(signature);
+
+    let mut seen = HashSet::new();
+    let mut candidates = Vec::new();
+    for band in 0..self.bands {
+      let bucket_key = self.band_hash(signature, band);
+      if let Some(ids) = self.buckets[band].get(&bucket_key) {
+        for &id in ids {
+          if seen.insert(id) {
+            candidates.push(id);
+          }
+        }
+      }
+    }
+    candidates
+  }
+
+  fn assert_signature_len(&self, signature: &[usize]) {
+    assert_eq!(
+      signature.len(),
+      self.bands * self.rows,
+      "signature length must equal bands * rows"
+    );
+  }
+
+  fn band_hash(&self, signature: &[usize], band: usize) -> u64 {
+    let start = band * self.rows;
+    let row_bytes: Vec<u8> = signature[start..start + self.rows]
+      .iter()
+      .flat_map(|&value| (value as u64).to_le_bytes())
+      .collect();
+    fnv1a_hash(&row_bytes)
+  }
+}
+
+/// Picks a `(bands, rows)` split of a length-`k` signature whose S-curve
+/// `1 - (1 - t^rows)^bands` threshold is closest to the target Jaccard
+/// threshold `t`.
+pub fn choose_bands_rows(k: usize, t: f64) -> (usize, usize) {
+  let mut best = (1, k);
+  let mut best_error = f64::MAX;
+
+  for rows in 1..=k {
+    if !k.is_multiple_of(rows) {
+      continue;
+    }
+    let bands = k / rows;
+    let approx_threshold = (1.0 / bands as f64).powf(1.0 / rows as f64);
+    let error = (approx_threshold - t).abs();
+    if error < best_error {
+      best_error = error;
+      best = (bands, rows);
+    }
+  }
+
+  best
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_insert_and_query_finds_shared_band() {
+    let mut index = LshIndex::new(2, 2);
+    index.insert(1, &[10, 20, 30, 40]);
+    index.insert(2, &[10, 20, 99, 99]);
+    index.insert(3, &[1, 2, 3, 4]);
+
+    let candidates = index.query(&[10, 20, 0, 0]);
+    assert!(candidates.contains(&1));
+    assert!(candidates.contains(&2));
+    assert!(!candidates.contains(&3));
+  }
+
+  #[test]
+  #[should_panic(expected = "bands * rows")]
+  fn test_insert_rejects_wrong_signature_length() {
+    let mut index = LshIndex::new(2, 2);
+    index.insert(1, &[1, 2, 3]);
+  }
+
+  #[test]
+  fn test_choose_bands_rows_divides_k() {
+    let (bands, rows) = choose_bands_rows(12, 0.5);
+    assert_eq!(bands * rows, 12);
+  }
+
+  #[test]
+  fn test_choose_bands_rows_prefers_stricter_split_for_higher_threshold() {
+    let (loose_bands, _) = choose_bands_rows(24, 0.2);
+    let (strict_bands, _) = choose_bands_rows(24, 0.9);
+    assert!(strict_bands <= loose_bands);
+  }
+}